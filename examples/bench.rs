@@ -1,13 +1,13 @@
-use funcfmt::{FormatMap, Render, ToFormatPieces};
+use funcfmt::{fm, FormatMap, Render, ToFormatPieces};
 use std::fmt::Write;
 
 fn main() {
-    let mut formatters: FormatMap<String> = FormatMap::new();
+    let mut formatters: FormatMap<String> = FormatMap::default();
     let mut fmtstr = String::new();
     let mut expected = String::new();
 
     for i in 1..20 {
-        formatters.insert(i.to_string().into(), |e| Some(format!("_{e}_")));
+        fm!(formatters, i.to_string(), |e| Some(format!("_{e}_")));
         write!(&mut fmtstr, "{{{}}}", i).unwrap();
         write!(&mut expected, "_bar_").unwrap();
     }
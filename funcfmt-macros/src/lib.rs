@@ -0,0 +1,215 @@
+//! The `template!` proc macro: precomputes a template's literal/placeholder layout at compile
+//! time, so fixed templates skip the char-by-char scan and bracket validation that
+//! `ToFormatPieces::to_format_pieces` otherwise repeats on every call.
+
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::quote;
+use syn::{
+    parse::{Parse, ParseStream},
+    parse_macro_input, Expr, LitStr, Token,
+};
+
+/// One element of a template, as scanned at compile time.
+#[cfg_attr(test, derive(Debug, PartialEq))]
+enum Piece {
+    /// A run of literal chars with no placeholders.
+    Literal(String),
+    /// A `{key}` placeholder.
+    Key(String),
+}
+
+struct TemplateInput {
+    map: Expr,
+    template: LitStr,
+}
+
+impl Parse for TemplateInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let map: Expr = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let template: LitStr = input.parse()?;
+        Ok(TemplateInput { map, template })
+    }
+}
+
+/// Precomputes the literal/placeholder layout of a template known at compile time.
+///
+/// `template!(fmap, "a{foo}e")` builds a `FormatPieces<T>` the same way
+/// `fmap.to_format_pieces("a{foo}e")` would, except the literal is scanned once, at compile time,
+/// instead of being re-parsed on every call, and only the `FormatMap` lookups happen at runtime.
+/// Imbalanced brackets are a compile error here rather than `Error::ImbalancedBrackets` at
+/// runtime. Unknown keys are still only discovered at runtime, since that depends on the
+/// contents of the `FormatMap` passed in.
+///
+/// Unlike `to_format_pieces`, a placeholder's format spec (the `:...` part) is not supported here
+/// and is treated as part of the key name.
+///
+/// # Example
+///
+/// ```
+/// use funcfmt::{fm, template, FormatMap, Render};
+///
+/// let mut fmap: FormatMap<String> = FormatMap::default();
+/// fm!(fmap, "foo", |e| Some(format!("b{e}d")));
+///
+/// let fp = template!(fmap, "a{foo}e").unwrap();
+/// assert_eq!(fp.render(&"c".to_string()), Ok("abcde".to_string()));
+/// ```
+///
+/// Imbalanced brackets are rejected at compile time rather than deferred to
+/// `Error::ImbalancedBrackets` at runtime:
+///
+/// ```compile_fail
+/// use funcfmt::{template, FormatMap};
+///
+/// let fmap: FormatMap<String> = FormatMap::default();
+/// template!(fmap, "{f{oo}二{bar}");
+/// ```
+#[proc_macro]
+pub fn template(input: TokenStream) -> TokenStream {
+    let TemplateInput { map, template } = parse_macro_input!(input as TemplateInput);
+    let tmpl = template.value();
+
+    let pieces = match parse_template(&tmpl) {
+        Ok(pieces) => pieces,
+        Err(msg) => {
+            return syn::Error::new(Span::call_site(), msg)
+                .to_compile_error()
+                .into();
+        }
+    };
+
+    let capacity = pieces
+        .iter()
+        .map(|p| match p {
+            Piece::Literal(s) => s.chars().count(),
+            Piece::Key(_) => 1,
+        })
+        .sum::<usize>();
+
+    let pushes = pieces.into_iter().map(|piece| match piece {
+        Piece::Literal(s) => {
+            let chars = s.chars();
+            quote! { #( out.push(::funcfmt::FormatPiece::Char(#chars)); )* }
+        }
+        Piece::Key(key) => quote! {
+            match __map.get(#key) {
+                ::std::option::Option::Some(f) => out.push(::funcfmt::FormatPiece::Formatter(
+                    ::funcfmt::Formatter {
+                        key: #key.to_string(),
+                        cb: *f,
+                        spec: ::std::option::Option::None,
+                    },
+                )),
+                ::std::option::Option::None => {
+                    return ::std::result::Result::Err(::funcfmt::Error::UnknownKey(#key.to_string()))
+                }
+            }
+        },
+    });
+
+    quote! {
+        (|| -> ::std::result::Result<::funcfmt::FormatPieces<_>, ::funcfmt::Error> {
+            let __map = &(#map);
+            let mut out = ::funcfmt::FormatPieces::with_capacity(#capacity);
+            #( #pushes )*
+            ::std::result::Result::Ok(out)
+        })()
+    }
+    .into()
+}
+
+/// Mirrors `ToFormatPieces::to_format_pieces`'s escaping rules (`{{`/`}}` escape a literal
+/// brace), but at compile time, returning a compile error message instead of
+/// `Error::ImbalancedBrackets`.
+fn parse_template(tmpl: &str) -> Result<Vec<Piece>, String> {
+    let mut out = Vec::new();
+    let mut literal = String::new();
+    let mut chars = tmpl.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                literal.push('{');
+            }
+            '{' => {
+                if !literal.is_empty() {
+                    out.push(Piece::Literal(std::mem::take(&mut literal)));
+                }
+                let mut key = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some('{') => return Err("imbalanced brackets in template".to_string()),
+                        Some(c) => key.push(c),
+                        None => return Err("imbalanced brackets in template".to_string()),
+                    }
+                }
+                out.push(Piece::Key(key));
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                literal.push('}');
+            }
+            '}' => return Err("imbalanced brackets in template".to_string()),
+            c => literal.push(c),
+        }
+    }
+
+    if !literal.is_empty() {
+        out.push(Piece::Literal(literal));
+    }
+
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn literal_and_key_pieces() {
+        assert_eq!(
+            parse_template("a{foo}e").unwrap(),
+            vec![
+                Piece::Literal("a".to_string()),
+                Piece::Key("foo".to_string()),
+                Piece::Literal("e".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn escaped_braces_are_literal() {
+        assert_eq!(
+            parse_template("{{foo}}").unwrap(),
+            vec![Piece::Literal("{foo}".to_string())]
+        );
+    }
+
+    #[test]
+    fn unescaped_brace_inside_key_is_imbalanced() {
+        assert_eq!(
+            parse_template("{f{oo}二{bar}"),
+            Err("imbalanced brackets in template".to_string())
+        );
+    }
+
+    #[test]
+    fn unclosed_key_is_imbalanced() {
+        assert_eq!(
+            parse_template("a{foo"),
+            Err("imbalanced brackets in template".to_string())
+        );
+    }
+
+    #[test]
+    fn unopened_close_is_imbalanced() {
+        assert_eq!(
+            parse_template("a}b"),
+            Err("imbalanced brackets in template".to_string())
+        );
+    }
+}
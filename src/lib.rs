@@ -24,14 +24,173 @@ pub enum Error {
     #[error("integer overflow/underflow")]
     Overflow,
 
+    /// A placeholder's format spec (the part after `:`) could not be parsed. Stores the offending
+    /// spec text.
+    #[error("bad format spec '{0}'")]
+    BadSpec(String),
+
+    /// A placeholder used the `{key:?}` debug form, but the key has no debug callback
+    /// registered. Stores the key name.
+    #[error("no debug callback for key '{0}'")]
+    NoDebugCallback(String),
+
     /// An error occurred during writing the result of the closure to the eventual output `String`.
     /// Stores the encapsulated error.
     #[error("std::fmt::Write error")]
     Write(#[from] std::fmt::Error),
 }
 
-/// A callback to be provided with data during rendering.
-pub type FormatterCallback<T> = fn(&T) -> Option<String>;
+/// The alignment requested by a placeholder's format spec, mirroring `std::fmt`'s `<`/`^`/`>`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Align {
+    Left,
+    Center,
+    Right,
+}
+
+/// A parsed `{key:[[fill]align][#][width][.precision][?]}` format spec.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct FormatSpec {
+    pub fill: char,
+    pub align: Align,
+    pub width: Option<usize>,
+    pub precision: Option<usize>,
+    /// Whether the `#` flag was given, requesting that multi-line callback output have its
+    /// continuation lines indented to the placeholder's output column.
+    pub alternate: bool,
+    /// Whether the placeholder used the trailing `?` debug form, e.g. `{key:?}`.
+    pub debug: bool,
+}
+
+impl FormatSpec {
+    fn align_char(c: char) -> Option<Align> {
+        match c {
+            '<' => Some(Align::Left),
+            '^' => Some(Align::Center),
+            '>' => Some(Align::Right),
+            _ => None,
+        }
+    }
+
+    /// Parses the portion of a placeholder following the `:` separator.
+    fn parse(spec: &str) -> Result<Self, Error> {
+        let debug = spec.ends_with('?');
+        let spec_body = if debug { &spec[..spec.len() - 1] } else { spec };
+
+        let chars: Vec<char> = spec_body.chars().collect();
+
+        let (fill, align, rest_idx) = if chars.len() >= 2 && Self::align_char(chars[1]).is_some()
+        {
+            (chars[0], Self::align_char(chars[1]).unwrap(), 2)
+        } else if chars.first().is_some_and(|&c| Self::align_char(c).is_some()) {
+            (' ', Self::align_char(chars[0]).unwrap(), 1)
+        } else {
+            (' ', Align::Left, 0)
+        };
+
+        let alternate = chars.get(rest_idx) == Some(&'#');
+        let rest_idx = if alternate { rest_idx + 1 } else { rest_idx };
+
+        let rest: String = chars[rest_idx..].iter().collect();
+        let (width_str, precision_str) = match rest.split_once('.') {
+            Some((w, p)) => (w, Some(p)),
+            None => (rest.as_str(), None),
+        };
+
+        let width = if width_str.is_empty() {
+            None
+        } else {
+            Some(
+                width_str
+                    .parse::<usize>()
+                    .map_err(|_| Error::BadSpec(spec.to_string()))?,
+            )
+        };
+
+        let precision = match precision_str {
+            None => None,
+            Some(p) if !p.is_empty() => {
+                Some(p.parse::<usize>().map_err(|_| Error::BadSpec(spec.to_string()))?)
+            }
+            Some(_) => return Err(Error::BadSpec(spec.to_string())),
+        };
+
+        Ok(FormatSpec {
+            fill,
+            align,
+            width,
+            precision,
+            alternate,
+            debug,
+        })
+    }
+
+    /// Applies precision (truncation) then width (padding) to a rendered value, matching
+    /// `std::fmt`'s behaviour of counting `char`s rather than bytes.
+    fn apply(&self, value: &str) -> String {
+        let truncated: String = match self.precision {
+            Some(p) => value.chars().take(p).collect(),
+            None => value.to_string(),
+        };
+
+        let len = truncated.chars().count();
+        let Some(width) = self.width else {
+            return truncated;
+        };
+        if width <= len {
+            return truncated;
+        }
+
+        let pad = width - len;
+        let fill = |n: usize| self.fill.to_string().repeat(n);
+        match self.align {
+            Align::Left => format!("{truncated}{}", fill(pad)),
+            Align::Right => format!("{}{truncated}", fill(pad)),
+            Align::Center => {
+                let left = pad / 2;
+                let right = pad - left;
+                format!("{}{truncated}{}", fill(left), fill(right))
+            }
+        }
+    }
+}
+
+/// The primary (non-debug) callback registered for a key, used to render `{key}`. `Str` is the
+/// original form, returning an owned `String` directly; `Display` accepts any `Display` value
+/// (e.g. a number or `&str`) so the callback doesn't have to allocate a `String` for it. The
+/// returned `Box` is bounded by the callback's own `&T` lifetime (rather than `'static`), so a
+/// callback can box a value borrowed from its argument, e.g. `&str` sliced out of a `String`
+/// field, instead of having to clone it.
+pub enum PrimaryCallback<T: ?Sized> {
+    Str(fn(&T) -> Option<String>),
+    Display(for<'a> fn(&'a T) -> Option<Box<dyn fmt::Display + 'a>>),
+}
+
+impl<T: ?Sized> Clone for PrimaryCallback<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+impl<T: ?Sized> Copy for PrimaryCallback<T> {}
+
+/// A callback producing the `Debug` form of a value, used to render `{key:?}`. Like
+/// `PrimaryCallback::Display`, the returned `Box` borrows from the callback's `&T` argument
+/// rather than requiring a `'static` value.
+pub type DebugCallback<T> = for<'a> fn(&'a T) -> Option<Box<dyn fmt::Debug + 'a>>;
+
+/// A callback to be provided with data during rendering. Bundles the primary (`{key}`) form with
+/// an optional debug (`{key:?}`) form for the same key.
+pub struct FormatterCallback<T: ?Sized> {
+    pub primary: PrimaryCallback<T>,
+    pub debug: Option<DebugCallback<T>>,
+}
+
+impl<T: ?Sized> Clone for FormatterCallback<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+impl<T: ?Sized> Copy for FormatterCallback<T> {}
 
 /// A mapping of keys to callback functions.
 pub type FormatMap<T> = FxHashMap<String, FormatterCallback<T>>;
@@ -43,6 +202,7 @@ pub type FormatPieces<T> = Vec<FormatPiece<T>>;
 pub struct Formatter<T: ?Sized> {
     pub key: String,
     pub cb: FormatterCallback<T>,
+    pub spec: Option<FormatSpec>,
 }
 
 impl<T> PartialEq for Formatter<T> {
@@ -58,6 +218,106 @@ impl<T> fmt::Debug for Formatter<T> {
     }
 }
 
+/// A `fmt::Write` adapter that tracks the number of `char`s written since the last `\n`, so a
+/// `#`-flagged `Formatter` knows what column its placeholder started at.
+struct ColumnTracker<'a, W: fmt::Write + ?Sized> {
+    out: &'a mut W,
+    column: usize,
+}
+
+impl<W: fmt::Write + ?Sized> fmt::Write for ColumnTracker<'_, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        match s.rfind('\n') {
+            Some(idx) => self.column = s[idx + 1..].chars().count(),
+            None => self.column += s.chars().count(),
+        }
+        self.out.write_str(s)
+    }
+}
+
+/// A `fmt::Write` adapter that injects `pad` spaces after every `\n`, used by the `#` flag to
+/// keep a multi-line callback result aligned with the column its placeholder started at.
+struct PadAdapter<'a, W: fmt::Write + ?Sized> {
+    out: &'a mut W,
+    pad: usize,
+}
+
+impl<W: fmt::Write + ?Sized> fmt::Write for PadAdapter<'_, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for (i, line) in s.split('\n').enumerate() {
+            if i > 0 {
+                self.out.write_char('\n')?;
+                for _ in 0..self.pad {
+                    self.out.write_char(' ')?;
+                }
+            }
+            self.out.write_str(line)?;
+        }
+        Ok(())
+    }
+}
+
+impl<T: ?Sized> Formatter<T> {
+    /// Invokes this placeholder's callback, applies its format spec (if any), and writes the
+    /// result to `out`.
+    fn write_to<W: fmt::Write + ?Sized>(
+        &self,
+        data: &T,
+        out: &mut ColumnTracker<'_, W>,
+    ) -> Result<(), Error> {
+        let no_data = || Error::NoData(self.key.to_string());
+
+        if self.spec.as_ref().is_some_and(|s| s.debug) {
+            let cb = self.debug_cb()?;
+            let value = cb(data).ok_or_else(no_data)?;
+            return self.write_value(out, format_args!("{value:?}"));
+        }
+
+        match self.cb.primary {
+            PrimaryCallback::Str(cb) => {
+                let value = cb(data).ok_or_else(no_data)?;
+                self.write_value(out, format_args!("{value}"))
+            }
+            PrimaryCallback::Display(cb) => {
+                let value = cb(data).ok_or_else(no_data)?;
+                self.write_value(out, format_args!("{value}"))
+            }
+        }
+    }
+
+    fn debug_cb(&self) -> Result<DebugCallback<T>, Error> {
+        self.cb
+            .debug
+            .ok_or_else(|| Error::NoDebugCallback(self.key.to_string()))
+    }
+
+    /// Writes `args` to `out`, applying this placeholder's width/precision/`#` indentation if
+    /// set. Writes straight through without an intermediate `String` when no such spec is
+    /// present.
+    fn write_value<W: fmt::Write + ?Sized>(
+        &self,
+        out: &mut ColumnTracker<'_, W>,
+        args: fmt::Arguments<'_>,
+    ) -> Result<(), Error> {
+        let Some(spec) = &self.spec else {
+            return Ok(out.write_fmt(args)?);
+        };
+        if spec.width.is_none() && spec.precision.is_none() && !spec.alternate {
+            return Ok(out.write_fmt(args)?);
+        }
+
+        let value = spec.apply(&args.to_string());
+        if spec.alternate && value.contains('\n') {
+            let pad = out.column;
+            let mut adapter = PadAdapter { out, pad };
+            adapter.write_str(&value)?;
+        } else {
+            out.write_str(&value)?;
+        }
+        Ok(())
+    }
+}
+
 /// Either a plain `Char`, or a function call back to be called later in `render`.
 #[derive(PartialEq, Eq, Debug)]
 pub enum FormatPiece<T> {
@@ -72,13 +332,26 @@ pub trait ToFormatPieces<T> {
     /// # Template format
     ///
     /// The template `tmpl` takes keys in the format `{foo}`, which will be replaced with the output
-    /// from the callback registered to key "foo". Callbacks return an `Option<String>`.
+    /// from the callback registered to key "foo". Callbacks return either an `Option<String>` or
+    /// an `Option` of any `Display` value, depending on how they were registered (see `fm!` and
+    /// `fm_display!`).
     ///
     /// If you want to return literal "{foo}", pass `{{foo}}`.
     ///
-    /// There are no restrictions on key names, other than that they cannot contain "{" or "}".
-    /// This is not enforced at construction time, but trying to use them will fail with
-    /// `Error::ImbalancedBrackets`.
+    /// There are no restrictions on key names, other than that they cannot contain "{", "}", or
+    /// ":". This is not enforced at construction time. A "{" or "}" in a placeholder fails with
+    /// `Error::ImbalancedBrackets`; a ":" always starts the format spec, so only the part before
+    /// the first ":" is looked up as the key (failing with `Error::BadSpec` if the rest is not a
+    /// valid spec, or `Error::UnknownKey` if that shorter key is not registered).
+    ///
+    /// # Format spec
+    ///
+    /// A placeholder may carry an optional format spec after a `:`, mirroring `std::fmt`'s
+    /// alignment grammar: `{key:[[fill]align][#][width][.precision]}`. `align` is one of `<`
+    /// (left), `^` (center), or `>` (right); `fill` is a single char immediately preceding
+    /// `align` (default space); `width` is a decimal minimum display width; `.precision`
+    /// truncates the rendered value to that many `char`s; `#` indents every line of a multi-line
+    /// callback result after the first to match the placeholder's output column.
     ///
     /// # Example
     ///
@@ -102,6 +375,9 @@ pub trait ToFormatPieces<T> {
     ///    escape)
     /// - `Error::Overflow` if internal string capacity calculation overflows
     /// - `Error::UnknownKey` if a requested key has no associated callback
+    /// - `Error::BadSpec` if a placeholder's format spec could not be parsed
+    /// - `Error::NoDebugCallback` if a placeholder uses the `{key:?}` form but no debug callback
+    ///   is registered for that key
     fn to_format_pieces<S: AsRef<str>>(&self, tmpl: S) -> Result<FormatPieces<T>, Error>;
 }
 
@@ -132,11 +408,20 @@ impl<T> ToFormatPieces<T> for FormatMap<T> {
                 ('}', 0) => return Err(Error::ImbalancedBrackets),
                 ('}', s) => {
                     let word = String::from_iter(&tmpl_vec[s..idx]);
-                    match self.get(&word) {
-                        Some(f) => {
-                            out.push(FormatPiece::Formatter(Formatter { key: word, cb: *f }))
+                    let (key, spec) = match word.split_once(':') {
+                        Some((key, spec)) => (key.to_string(), Some(FormatSpec::parse(spec)?)),
+                        None => (word, None),
+                    };
+                    match self.get(&key) {
+                        Some(f) if spec.as_ref().is_some_and(|s| s.debug) && f.debug.is_none() => {
+                            return Err(Error::NoDebugCallback(key))
                         }
-                        None => return Err(Error::UnknownKey(word)),
+                        Some(f) => out.push(FormatPiece::Formatter(Formatter {
+                            key,
+                            cb: *f,
+                            spec,
+                        })),
+                        None => return Err(Error::UnknownKey(key)),
                     };
                     start_word_idx = 0;
                 }
@@ -172,27 +457,43 @@ pub trait Render<T: ?Sized> {
     /// - `Error::Overflow` if internal string capacity calculation overflows
     /// - `Error::Write` if writing to the output `String` fails
     fn render(&self, data: &T) -> Result<String, Error>;
+
+    /// Given some data, render the given format pieces directly into `out`, rather than
+    /// allocating a fresh `String`. Useful for rendering into a buffer reused across a hot
+    /// loop, or into an adapter over another writer (e.g. `io::Write`, via a wrapper that
+    /// implements `fmt::Write`).
+    ///
+    /// `render` is implemented in terms of this method.
+    ///
+    /// # Errors
+    ///
+    /// - `Error::NoData` if the callback returns `None`
+    /// - `Error::Write` if writing to `out` fails
+    fn render_to<W: fmt::Write>(&self, data: &T, out: &mut W) -> Result<(), Error>;
 }
 
 impl<T> Render<T> for FormatPieces<T> {
     fn render(&self, data: &T) -> Result<String, Error> {
         // Ballpark guess large enough to usually avoid extra allocations
         let mut out = String::with_capacity(self.len().checked_mul(4).ok_or(Error::Overflow)?);
+        self.render_to(data, &mut out)?;
+        Ok(out)
+    }
+
+    fn render_to<W: fmt::Write>(&self, data: &T, out: &mut W) -> Result<(), Error> {
+        let mut out = ColumnTracker { out, column: 0 };
         for piece in self {
             match piece {
-                FormatPiece::Char(c) => out.push(*c),
-                FormatPiece::Formatter(f) => write!(
-                    &mut out,
-                    "{}",
-                    (f.cb)(data).ok_or_else(|| Error::NoData(f.key.to_string()))?
-                )?,
+                FormatPiece::Char(c) => out.write_char(*c)?,
+                FormatPiece::Formatter(f) => f.write_to(data, &mut out)?,
             }
         }
-        Ok(out)
+        Ok(())
     }
 }
 
-/// Convenience macro to construct a single mapping for a `FormatMap`.
+/// Convenience macro to register a `Str`-returning callback (the original form, returning an
+/// owned `String`) for a key in a `FormatMap`.
 ///
 /// # Example
 ///
@@ -203,13 +504,102 @@ impl<T> Render<T> for FormatPieces<T> {
 /// let fmap: FormatMap<String> = FormatMap::defaults();
 /// fm!(fmap, "foo", |data| Some(format!("b{data}d")));
 /// ```
+///
+/// Re-registering an already-registered key preserves any `debug` callback attached to it via
+/// `fm_debug!`, rather than silently discarding it.
 #[macro_export]
 macro_rules! fm {
-    ($map:ident, $key:expr, $cb:expr) => {
-        $map.insert($key.to_string(), $cb as $crate::FormatterCallback<_>)
-    };
+    ($map:ident, $key:expr, $cb:expr) => {{
+        let key = $key.to_string();
+        let debug = $map.get(&key).and_then(|entry| entry.debug);
+        $map.insert(
+            key,
+            $crate::FormatterCallback {
+                primary: $crate::PrimaryCallback::Str($cb as fn(&_) -> Option<String>),
+                debug,
+            },
+        )
+    }};
 }
 
+/// Convenience macro to register a `Display`-returning callback for a key in a `FormatMap`,
+/// avoiding a `String` allocation for values that already implement `Display`. Unlike `fm!`, the
+/// callback returns a boxed `Display` value rather than an owned `String`, so it can box a value
+/// borrowed from its `&T` argument instead of having to clone it, e.g. slicing a `&str` out of a
+/// `String` field.
+///
+/// The callback must box its return value itself (`Some(Box::new(data.len()))` rather than
+/// `Some(data.len())`): `Display` needs a callback whose signature is generic over the lifetime
+/// of its `&T` argument, and a closure that calls out to another closure to do the boxing can't
+/// be inferred as such, so the cast to a function pointer has to apply to the callback directly.
+///
+/// # Example
+///
+/// ```
+/// use funcfmt::{fm_display, FormatMap};
+///
+/// struct Person { name: String }
+///
+/// let mut fmap: FormatMap<Person> = FormatMap::default();
+/// fm_display!(fmap, "name", |data: &Person| Some(Box::new(data.name.as_str())));
+/// ```
+///
+/// Like `fm!`, re-registering an already-registered key preserves any `debug` callback attached
+/// to it via `fm_debug!`, rather than silently discarding it.
+#[macro_export]
+macro_rules! fm_display {
+    ($map:ident, $key:expr, $cb:expr) => {{
+        let key = $key.to_string();
+        let debug = $map.get(&key).and_then(|entry| entry.debug);
+        $map.insert(
+            key,
+            $crate::FormatterCallback {
+                primary: $crate::PrimaryCallback::Display(
+                    $cb as for<'a> fn(&'a _) -> Option<Box<dyn ::std::fmt::Display + 'a>>,
+                ),
+                debug,
+            },
+        )
+    }};
+}
+
+/// Convenience macro to attach a `Debug`-form callback to an already-registered key, selected by
+/// the `{key:?}` placeholder form. Must be called after `fm!` or `fm_display!` has registered the
+/// key's primary callback.
+///
+/// # Example
+///
+/// ```
+/// use funcfmt::{fm, fm_debug, FormatMap};
+///
+/// let mut fmap: FormatMap<Vec<u8>> = FormatMap::default();
+/// fm!(fmap, "foo", |data: &Vec<u8>| Some(format!("{} bytes", data.len())));
+/// fm_debug!(fmap, "foo", |data: &Vec<u8>| Some(Box::new(data.clone())));
+/// ```
+///
+/// Like `fm_display!`, the callback must box its return value itself, for the same lifetime
+/// reasons.
+///
+/// # Panics
+///
+/// Panics if `$key` has no registered primary callback, since otherwise the debug callback would
+/// silently go nowhere.
+#[macro_export]
+macro_rules! fm_debug {
+    ($map:ident, $key:expr, $cb:expr) => {{
+        let key = $key.to_string();
+        let entry = $map
+            .get_mut(&key)
+            .unwrap_or_else(|| panic!("fm_debug!: key {key:?} has no registered primary callback (call fm!/fm_display! first)"));
+        entry.debug = Some($cb as for<'a> fn(&'a _) -> Option<Box<dyn ::std::fmt::Debug + 'a>>);
+    }};
+}
+
+/// Precomputes a template's literal/placeholder layout at compile time instead of re-scanning it
+/// on every call. See `funcfmt_macros::template` for details.
+#[doc(inline)]
+pub use funcfmt_macros::template;
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -221,6 +611,10 @@ mod tests {
             fm!(f, "foo", |e| Some(format!("{e} foo {e}")));
             fm!(f, "bar", |e| Some(format!("{e} bar {e}")));
             fm!(f, "nodata", |_| None);
+            fm_display!(f, "len", |e: &String| Some(Box::new(e.len())));
+            fm!(f, "list", |e: &String| Some(e.clone()));
+            fm_debug!(f, "list", |e: &String| Some(Box::new(e.chars().collect::<Vec<_>>())));
+            fm!(f, "multiline", |_| Some("a\nb\nc".to_string()));
             f
         };
     }
@@ -233,6 +627,53 @@ mod tests {
         assert_eq!(fmt, Ok("一bar foo bar二bar bar bar".to_owned()));
     }
 
+    #[test]
+    fn render_to_matches_render() {
+        let inp = String::from("bar");
+        let fp = FORMATTERS.to_format_pieces("一{foo}二{bar}").unwrap();
+
+        let mut out = String::new();
+        fp.render_to(&inp, &mut out).unwrap();
+
+        assert_eq!(out, fp.render(&inp).unwrap());
+    }
+
+    #[test]
+    fn display_callback_renders_without_string_alloc_in_callback() {
+        let inp = String::from("bar");
+        let fp = FORMATTERS.to_format_pieces("{len}").unwrap();
+        assert_eq!(fp.render(&inp), Ok("3".to_string()));
+    }
+
+    #[test]
+    fn debug_form_uses_debug_callback() {
+        let inp = String::from("ab");
+        let fp = FORMATTERS.to_format_pieces("{list:?}").unwrap();
+        assert_eq!(fp.render(&inp), Ok(format!("{:?}", vec!['a', 'b'])));
+    }
+
+    #[test]
+    fn debug_form_without_registered_debug_callback_errors() {
+        assert_eq!(
+            FORMATTERS.to_format_pieces("{foo:?}"),
+            Err(Error::NoDebugCallback("foo".to_string()))
+        );
+    }
+
+    #[test]
+    fn alternate_flag_indents_continuation_lines_to_placeholder_column() {
+        let inp = String::from("bar");
+        let fp = FORMATTERS.to_format_pieces("pre: {multiline:#}").unwrap();
+        assert_eq!(fp.render(&inp), Ok("pre: a\n     b\n     c".to_string()));
+    }
+
+    #[test]
+    fn alternate_flag_without_newlines_is_a_no_op() {
+        let inp = String::from("bar");
+        let fp = FORMATTERS.to_format_pieces("{foo:#}").unwrap();
+        assert_eq!(fp.render(&inp), Ok("bar foo bar".to_string()));
+    }
+
     #[test]
     fn imbalance_open() {
         assert_eq!(
@@ -298,20 +739,29 @@ mod tests {
 
     #[test]
     fn formatter_eq_based_on_key_only() {
-        let c1: FormatterCallback<String> = |e| Some(e.to_string());
-        let c2: FormatterCallback<String> = |e| Some(e.to_string());
+        let c1 = FormatterCallback {
+            primary: PrimaryCallback::Str(|e: &String| Some(e.to_string())),
+            debug: None,
+        };
+        let c2 = FormatterCallback {
+            primary: PrimaryCallback::Str(|e: &String| Some(e.to_string())),
+            debug: None,
+        };
 
         let f1 = Formatter {
             key: "foo".to_string(),
             cb: c1,
+            spec: None,
         };
         let f2 = Formatter {
             key: "foo".to_string(),
             cb: c2,
+            spec: None,
         };
         let b1 = Formatter {
             key: "bar".to_string(),
             cb: c1,
+            spec: None,
         };
 
         assert_eq!(f1, f2);
@@ -320,11 +770,73 @@ mod tests {
 
     #[test]
     fn formatter_debug() {
-        let c1: FormatterCallback<String> = |e| Some(e.to_string());
+        let c1 = FormatterCallback {
+            primary: PrimaryCallback::Str(|e: &String| Some(e.to_string())),
+            debug: None,
+        };
         let f1 = Formatter {
             key: "foo".to_string(),
             cb: c1,
+            spec: None,
         };
         assert_eq!(format!("{:?}", f1), "Formatter(key: foo)");
     }
+
+    #[test]
+    fn spec_width_and_align() {
+        let inp = String::from("bar");
+        let fp = FORMATTERS.to_format_pieces("{foo:>20}").unwrap();
+        assert_eq!(
+            fp.render(&inp),
+            Ok(format!("{:>20}", "bar foo bar"))
+        );
+    }
+
+    #[test]
+    fn spec_fill_and_center() {
+        let inp = String::from("bar");
+        let fp = FORMATTERS.to_format_pieces("{foo:*^20}").unwrap();
+        assert_eq!(
+            fp.render(&inp),
+            Ok(format!("{:*^20}", "bar foo bar"))
+        );
+    }
+
+    #[test]
+    fn spec_precision_truncates_by_char() {
+        let inp = String::from("一");
+        let fp = FORMATTERS.to_format_pieces("{foo:.3}").unwrap();
+        assert_eq!(fp.render(&inp), Ok("一 f".to_string()));
+    }
+
+    #[test]
+    fn spec_bad_spec_errors() {
+        assert_eq!(
+            FORMATTERS.to_format_pieces("{foo:potato}"),
+            Err(Error::BadSpec("potato".to_string()))
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "fm_debug!: key \"foo\" has no registered primary callback")]
+    fn fm_debug_panics_on_unregistered_key() {
+        let mut fmap: FormatMap<String> = FormatMap::default();
+        fm_debug!(fmap, "foo", |e: &String| Some(Box::new(e.clone())));
+    }
+
+    #[test]
+    fn fm_reregistration_preserves_debug_callback() {
+        let mut fmap: FormatMap<String> = FormatMap::default();
+        fm!(fmap, "foo", |e| Some(format!("{e} one")));
+        fm_debug!(fmap, "foo", |e: &String| Some(Box::new(e.clone())));
+
+        fm!(fmap, "foo", |e| Some(format!("{e} two")));
+
+        let inp = String::from("bar");
+        let fp = fmap.to_format_pieces("{foo}").unwrap();
+        assert_eq!(fp.render(&inp), Ok("bar two".to_string()));
+
+        let fp = fmap.to_format_pieces("{foo:?}").unwrap();
+        assert_eq!(fp.render(&inp), Ok(format!("{:?}", "bar".to_string())));
+    }
 }
@@ -0,0 +1,41 @@
+//! Integration tests for the `template!` proc macro. These live here rather than in
+//! `src/lib.rs`'s `tests` module because `template!` expands to `::funcfmt::...` paths, which
+//! only resolve from outside the `funcfmt` crate itself.
+
+use funcfmt::{fm, template, FormatMap, Render, ToFormatPieces};
+
+fn formatters() -> FormatMap<String> {
+    let mut f = FormatMap::default();
+    fm!(f, "foo", |e| Some(format!("{e} foo {e}")));
+    fm!(f, "bar", |e| Some(format!("{e} bar {e}")));
+    f
+}
+
+#[test]
+fn matches_to_format_pieces() {
+    let fmap = formatters();
+    let inp = String::from("bar");
+
+    let fp = template!(fmap, "一{foo}二{bar}").unwrap();
+    let expected = fmap.to_format_pieces("一{foo}二{bar}").unwrap();
+
+    assert_eq!(fp.render(&inp), expected.render(&inp));
+}
+
+#[test]
+fn escapes_braces() {
+    let fmap = formatters();
+    let inp = String::from("bar");
+
+    let fp = template!(fmap, "一{{foo}}二").unwrap();
+    assert_eq!(fp.render(&inp), Ok("一{foo}二".to_string()));
+}
+
+#[test]
+fn unknown_key_errors_at_runtime() {
+    let fmap = formatters();
+    assert_eq!(
+        template!(fmap, "{baz}"),
+        Err(funcfmt::Error::UnknownKey("baz".to_string()))
+    );
+}